@@ -0,0 +1,67 @@
+//! Supplies answers to the config wizard's prompts, either from a live
+//! terminal or replayed from a pre-seeded list. The latter is what makes
+//! `--config-answers`/`HYFETCH_CONFIG_ANSWERS` and golden-file tests of the
+//! wizard possible.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+
+use crate::utils::input;
+
+/// A source of answers for the prompts in `create_config`.
+pub trait AnswerSource {
+    /// Returns the next answer, prompting with `prompt` first if this source
+    /// is interactive.
+    fn next_answer(&mut self, prompt: Option<&str>) -> Result<String>;
+}
+
+/// Reads answers from stdin, prompting as it goes. The default for a real
+/// terminal session.
+pub struct StdinAnswerSource;
+
+impl AnswerSource for StdinAnswerSource {
+    fn next_answer(&mut self, prompt: Option<&str>) -> Result<String> {
+        input(prompt)
+    }
+}
+
+/// Replays a fixed, ordered list of answers instead of reading from stdin,
+/// for `--config-answers`/`HYFETCH_CONFIG_ANSWERS` and integration tests.
+pub struct ReplayAnswerSource {
+    answers: VecDeque<String>,
+}
+
+impl ReplayAnswerSource {
+    /// Builds a replay source from an in-memory list of answers, in order.
+    pub fn new(answers: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            answers: answers.into_iter().collect(),
+        }
+    }
+
+    /// Builds a replay source from a file of newline-separated answers.
+    /// Blank lines and lines starting with `#` are skipped, so answer files
+    /// can be commented.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read config answers from {path:?}"))?;
+        Ok(Self::new(
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(ToOwned::to_owned),
+        ))
+    }
+}
+
+impl AnswerSource for ReplayAnswerSource {
+    fn next_answer(&mut self, _prompt: Option<&str>) -> Result<String> {
+        self.answers
+            .pop_front()
+            .context("ran out of replayed config answers")
+    }
+}
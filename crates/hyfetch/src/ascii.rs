@@ -0,0 +1,68 @@
+//! Distro ascii art: the raw form as shipped by neofetch/fastfetch (with
+//! `${c1}`-style color placeholders) and the normalized form (a rectangular
+//! grid of lines, ready to be recolored).
+
+use anyhow::{Context as _, Result};
+
+use crate::color_util::NeofetchAsciiIndexedColor;
+use crate::neofetch_util::ColorAlignment;
+use crate::presets::ColorProfile;
+use crate::types::{AnsiMode, TerminalTheme};
+
+/// Ascii art as shipped by the backend, with embedded neofetch color codes
+/// and an (unused outside of neofetch) foreground color list.
+#[derive(Clone, Debug)]
+pub struct RawAsciiArt {
+    pub asc: String,
+    pub fg: Vec<NeofetchAsciiIndexedColor>,
+}
+
+impl RawAsciiArt {
+    /// Strips trailing whitespace from each line and pads every line to the
+    /// width of the widest one, so later recoloring can index into a
+    /// rectangular grid.
+    pub fn to_normalized(&self) -> Result<NormalizedAsciiArt> {
+        let lines: Vec<String> = self.asc.lines().map(|l| l.trim_end().to_owned()).collect();
+        let w = lines
+            .iter()
+            .map(|l| l.chars().count())
+            .max()
+            .context("ascii art should not be empty")?;
+        let lines: Vec<String> = lines
+            .into_iter()
+            .map(|l| {
+                let pad = w - l.chars().count();
+                format!("{l}{}", " ".repeat(pad))
+            })
+            .collect();
+        let w: u16 = w.try_into().context("ascii art width should fit in u16")?;
+        let h: u16 = lines.len().try_into().context("ascii art height should fit in u16")?;
+        Ok(NormalizedAsciiArt { lines, w, h })
+    }
+}
+
+/// Ascii art normalized to a rectangular grid of lines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NormalizedAsciiArt {
+    pub lines: Vec<String>,
+    pub w: u16,
+    pub h: u16,
+}
+
+impl NormalizedAsciiArt {
+    /// Recolors the neofetch color placeholders in this ascii art according
+    /// to `color_align` and `color_profile`.
+    pub fn to_recolored(
+        &self,
+        color_align: &ColorAlignment,
+        color_profile: &ColorProfile,
+        color_mode: AnsiMode,
+        theme: TerminalTheme,
+    ) -> Result<NormalizedAsciiArt> {
+        let _ = theme;
+        let lines = color_align
+            .recolor_ascii(&self.lines, color_profile, color_mode)
+            .context("failed to recolor ascii lines")?;
+        Ok(NormalizedAsciiArt { lines, w: self.w, h: self.h })
+    }
+}
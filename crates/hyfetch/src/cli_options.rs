@@ -0,0 +1,109 @@
+//! Command-line argument parsing.
+
+use std::path::PathBuf;
+
+use bpaf::Bpaf;
+
+use crate::color_util::Lightness;
+use crate::pager::PagingMode;
+use crate::types::{AnsiMode, Backend, ColorWhen};
+use crate::utils::get_default_config_path;
+
+/// Parsed command-line options.
+#[derive(Debug, Clone, Bpaf)]
+#[bpaf(options, version)]
+pub struct Options {
+    /// Enable debug logging for hyfetch's own targets (this replaces any
+    /// level `RUST_LOG` sets for the `hyfetch` target itself).
+    #[bpaf(long, short)]
+    pub debug: bool,
+
+    /// When to emit ANSI colors: `auto` (the default) follows the terminal
+    /// and `NO_COLOR`, `always` forces color even when piped, `never`
+    /// produces plain text.
+    #[bpaf(long("color"), argument("WHEN"), fallback(ColorWhen::Auto))]
+    pub color: ColorWhen,
+
+    /// Run the configuration wizard instead of using the saved config.
+    #[bpaf(long)]
+    pub config: bool,
+
+    /// Path to the config file to load/save.
+    #[bpaf(long, fallback(get_default_config_path()))]
+    pub config_file: PathBuf,
+
+    /// Path to a file of newline-separated answers that drives the config
+    /// wizard non-interactively instead of prompting on stdin (also read
+    /// from `HYFETCH_CONFIG_ANSWERS` if this is unset).
+    #[bpaf(long, argument("FILE"))]
+    pub config_answers: Option<PathBuf>,
+
+    /// Print distro ascii art and exit.
+    #[bpaf(long)]
+    pub test_print: bool,
+
+    /// Print the font-based logo and exit.
+    #[bpaf(long)]
+    pub print_font_logo: bool,
+
+    /// Use a custom distro's ascii art/name instead of auto-detecting.
+    #[bpaf(long, argument("DISTRO"))]
+    pub distro: Option<String>,
+
+    /// Path to a raw ascii art file to use instead of the distro's.
+    #[bpaf(long, argument("FILE"))]
+    pub ascii_file: Option<PathBuf>,
+
+    /// Preset (built-in or user-defined) to use, overriding the saved config.
+    #[bpaf(long, argument("PRESET"))]
+    pub preset: Option<String>,
+
+    /// Color rendering mode, overriding the saved config.
+    #[bpaf(long, argument("MODE"))]
+    pub mode: Option<AnsiMode>,
+
+    /// *fetch backend to use, overriding the saved config.
+    #[bpaf(long, argument("BACKEND"))]
+    pub backend: Option<Backend>,
+
+    /// Scale the preset's lightness by this factor.
+    #[bpaf(long, argument("SCALE"))]
+    pub scale: Option<f32>,
+
+    /// Replace the preset's lightness with this value (0.0-1.0).
+    #[bpaf(long, argument("LIGHTNESS"), parse(parse_lightness_arg))]
+    pub lightness: Option<Lightness>,
+
+    /// Detect the terminal's background and pick a light/dark theme.
+    #[bpaf(long, argument("BOOL"))]
+    pub auto_detect_light_dark: Option<bool>,
+
+    /// Force the pride month animation regardless of the date.
+    #[bpaf(long)]
+    pub june: bool,
+
+    /// Wait for a keypress before exiting.
+    #[bpaf(long)]
+    pub ask_exit: bool,
+
+    /// When to page output (the rendered fetch output, and the flag browser)
+    /// through `$PAGER`, overriding the saved config.
+    #[bpaf(long, argument("MODE"))]
+    pub paging: Option<PagingMode>,
+
+    /// Also push the chosen preset's colors into the Linux virtual console's
+    /// 16-color palette, theming the whole TTY.
+    #[cfg(all(target_os = "linux", feature = "vtcol"))]
+    #[bpaf(long)]
+    pub apply_vtcol: bool,
+
+    /// Extra arguments forwarded to the *fetch backend.
+    #[bpaf(positional("ARGS"))]
+    pub args: Option<Vec<String>>,
+}
+
+fn parse_lightness_arg(s: String) -> Result<Lightness, String> {
+    s.parse::<f32>()
+        .map_err(|e| e.to_string())
+        .and_then(|v| Lightness::new(v).map_err(|e| e.to_string()))
+}
@@ -0,0 +1,350 @@
+//! Color rendering: the `&`-code markup language used for titles/prompts, and
+//! conversion from [`Srgb`] to ANSI escape sequences.
+
+use std::io::{self, Write as _};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+use palette::{FromColor as _, Lab, LinSrgb, Srgb};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{AnsiMode, TerminalTheme};
+
+/// Whether ANSI color/markup escapes should be emitted at all, independent of
+/// [`AnsiMode`]. Set once at startup from `--color`/`NO_COLOR` via
+/// [`set_color_enabled`]; defaults to enabled so library consumers that never
+/// call it (e.g. tests) keep the previous behavior.
+static COLOR_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Enables or disables all color/markup output globally. `main` calls this
+/// once, early, based on `--color`, `NO_COLOR`, and whether stdout is a TTY.
+pub fn set_color_enabled(enabled: bool) {
+    COLOR_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether color output is currently enabled.
+pub fn color_enabled() -> bool {
+    COLOR_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Whether a color is being used to paint text or its background.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForegroundBackground {
+    Foreground,
+    Background,
+}
+
+/// An index into a [`crate::presets::ColorProfile`]'s list of colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PresetIndexedColor(pub u8);
+
+impl From<u8> for PresetIndexedColor {
+    fn from(i: u8) -> Self {
+        Self(i)
+    }
+}
+
+/// An index parsed out of a neofetch ASCII art's `${c1}`-style color placeholders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NeofetchAsciiIndexedColor(pub u8);
+
+impl std::str::FromStr for NeofetchAsciiIndexedColor {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+/// A validated lightness value in `[0.0, 1.0]`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(try_from = "f32", into = "f32")]
+pub struct Lightness(f32);
+
+impl TryFrom<f32> for Lightness {
+    type Error = anyhow::Error;
+
+    fn try_from(value: f32) -> Result<Self> {
+        Self::new(value)
+    }
+}
+
+impl Lightness {
+    pub fn new(value: f32) -> Result<Self> {
+        if !(0.0..=1.0).contains(&value) {
+            anyhow::bail!("lightness {value} is out of range [0, 1]");
+        }
+        Ok(Self(value))
+    }
+}
+
+impl From<Lightness> for f32 {
+    fn from(lightness: Lightness) -> Self {
+        lightness.0
+    }
+}
+
+/// Picks a readable black/white foreground for a given background color.
+pub trait ContrastGrayscale {
+    fn contrast_grayscale(&self) -> Self;
+}
+
+impl ContrastGrayscale for Srgb<u8> {
+    fn contrast_grayscale(&self) -> Self {
+        let luma = 0.299 * f32::from(self.red)
+            + 0.587 * f32::from(self.green)
+            + 0.114 * f32::from(self.blue);
+        if luma > 127.0 {
+            Srgb::new(0, 0, 0)
+        } else {
+            Srgb::new(255, 255, 255)
+        }
+    }
+}
+
+/// Classifies a color as belonging to a light or dark theme.
+pub trait Theme {
+    fn theme(&self) -> TerminalTheme;
+}
+
+impl Theme for Srgb<u8> {
+    fn theme(&self) -> TerminalTheme {
+        let lab = Lab::from_color(self.into_format::<f32>());
+        if lab.l >= 50.0 {
+            TerminalTheme::Light
+        } else {
+            TerminalTheme::Dark
+        }
+    }
+}
+
+/// Renders a color as an ANSI SGR escape sequence in the given [`AnsiMode`].
+pub trait ToAnsiString {
+    fn to_ansi_string(&self, mode: AnsiMode, fb: ForegroundBackground) -> String;
+}
+
+impl ToAnsiString for Srgb<u8> {
+    fn to_ansi_string(&self, mode: AnsiMode, fb: ForegroundBackground) -> String {
+        if !color_enabled() {
+            return String::new();
+        }
+        match mode {
+            AnsiMode::Rgb => {
+                let code = sgr_base(fb);
+                format!("\x1b[{code};2;{r};{g};{b}m", r = self.red, g = self.green, b = self.blue)
+            },
+            AnsiMode::Ansi256 => {
+                let idx = nearest_ansi256_index(*self);
+                let code = match fb {
+                    ForegroundBackground::Foreground => 38,
+                    ForegroundBackground::Background => 48,
+                };
+                format!("\x1b[{code};5;{idx}m")
+            },
+            AnsiMode::Ansi16 => {
+                let (base, bright) = nearest_ansi16_index(*self);
+                let code = match (fb, bright) {
+                    (ForegroundBackground::Foreground, false) => 30 + base,
+                    (ForegroundBackground::Foreground, true) => 90 + base,
+                    (ForegroundBackground::Background, false) => 40 + base,
+                    (ForegroundBackground::Background, true) => 100 + base,
+                };
+                format!("\x1b[{code}m")
+            },
+        }
+    }
+}
+
+fn sgr_base(fb: ForegroundBackground) -> u8 {
+    match fb {
+        ForegroundBackground::Foreground => 38,
+        ForegroundBackground::Background => 48,
+    }
+}
+
+/// The per-channel levels of the 6x6x6 xterm-256 color cube (indices 16-231).
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Maps an RGB color to the closest xterm-256 palette index (16-231 for the
+/// 6x6x6 color cube, 232-255 for the grayscale ramp), by squared Euclidean
+/// distance to each candidate's actual palette RGB.
+fn nearest_ansi256_index(color: Srgb<u8>) -> u8 {
+    fn sq_dist(a: (u16, u16, u16), b: (u8, u8, u8)) -> u32 {
+        let dr = i32::from(a.0) - i32::from(b.0);
+        let dg = i32::from(a.1) - i32::from(b.1);
+        let db = i32::from(a.2) - i32::from(b.2);
+        (dr * dr + dg * dg + db * db) as u32
+    }
+
+    let target = (u16::from(color.red), u16::from(color.green), u16::from(color.blue));
+
+    let nearest_level = |channel: u8| {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, &level)| i32::from(channel).abs_diff(i32::from(level)))
+            .map(|(i, &level)| (i, level))
+            .expect("`CUBE_LEVELS` should not be empty")
+    };
+    let (rl, r) = nearest_level(color.red);
+    let (gl, g) = nearest_level(color.green);
+    let (bl, b) = nearest_level(color.blue);
+    let cube_idx = 16 + 36 * rl + 6 * gl + bl;
+    let cube_dist = sq_dist(target, (r, g, b));
+
+    let (gray_idx, gray_dist) = (0..24_usize)
+        .map(|i| {
+            let value = u8::try_from(8 + 10 * i).expect("gray ramp value should fit in u8");
+            (232 + i, sq_dist(target, (value, value, value)))
+        })
+        .min_by_key(|&(_, dist)| dist)
+        .expect("there should be 24 grayscale ramp entries");
+
+    u8::try_from(if cube_dist <= gray_dist { cube_idx } else { gray_idx })
+        .expect("xterm-256 index should fit in u8")
+}
+
+/// The standard 16-color ANSI palette, in SGR order: the 8 normal colors
+/// (black, red, green, yellow, blue, magenta, cyan, white) followed by their
+/// bright counterparts.
+const ANSI16_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (170, 0, 0),
+    (0, 170, 0),
+    (170, 85, 0),
+    (0, 0, 170),
+    (170, 0, 170),
+    (0, 170, 170),
+    (170, 170, 170),
+    (85, 85, 85),
+    (255, 85, 85),
+    (85, 255, 85),
+    (255, 255, 85),
+    (85, 85, 255),
+    (255, 85, 255),
+    (85, 255, 255),
+    (255, 255, 255),
+];
+
+/// Finds the nearest [`ANSI16_PALETTE`] entry to `color` by squared Euclidean
+/// distance in linear RGB (to avoid gamma bias), returning its base SGR index
+/// (`0..8`) and whether it's the bright variant.
+fn nearest_ansi16_index(color: Srgb<u8>) -> (u8, bool) {
+    let target: LinSrgb = color.into_format::<f32>().into_linear();
+    let (idx, _) = ANSI16_PALETTE
+        .iter()
+        .map(|&(r, g, b)| Srgb::new(r, g, b).into_format::<f32>().into_linear())
+        .enumerate()
+        .map(|(i, c): (usize, LinSrgb)| {
+            let dr = c.red - target.red;
+            let dg = c.green - target.green;
+            let db = c.blue - target.blue;
+            (i, dr * dr + dg * dg + db * db)
+        })
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).expect("distance should not be NaN"))
+        .expect("`ANSI16_PALETTE` should not be empty");
+    (u8::try_from(idx % 8).unwrap(), idx >= 8)
+}
+
+/// Translates one `&`-prefixed markup code (e.g. `&a`, `&l`, `&~`) to a raw ANSI
+/// escape sequence. Returns `None` for an unrecognized code, in which case the
+/// `&` and the following character are emitted verbatim.
+fn markup_code_to_ansi(code: char) -> Option<&'static str> {
+    Some(match code {
+        '0' => "\x1b[30m",
+        '1' => "\x1b[34m",
+        '2' => "\x1b[32m",
+        '3' => "\x1b[36m",
+        '4' => "\x1b[31m",
+        '5' => "\x1b[35m",
+        '6' => "\x1b[33m",
+        '7' => "\x1b[37m",
+        '8' => "\x1b[90m",
+        '9' => "\x1b[94m",
+        'a' => "\x1b[92m",
+        'b' => "\x1b[96m",
+        'c' => "\x1b[91m",
+        'd' => "\x1b[95m",
+        'e' => "\x1b[93m",
+        'f' => "\x1b[97m",
+        'l' => "\x1b[1m",
+        'n' => "\x1b[4m",
+        'r' | '~' | 'L' => "\x1b[0m",
+        _ => return None,
+    })
+}
+
+/// Expands `&`-code markup in `s` into ANSI escape sequences for `mode`. When
+/// color output is disabled (see [`set_color_enabled`]), `&`-codes are
+/// stripped instead of translated, so the result is plain, escape-free text.
+pub fn color(s: impl AsRef<str>, mode: AnsiMode) -> Result<String> {
+    let s = s.as_ref();
+    let _ = mode;
+    let plain = !color_enabled();
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '&' {
+            out.push(c);
+            continue;
+        }
+        let Some(code) = chars.next() else {
+            out.push('&');
+            break;
+        };
+        match markup_code_to_ansi(code) {
+            Some(ansi) => {
+                if !plain {
+                    out.push_str(ansi);
+                }
+            },
+            None => {
+                out.push('&');
+                out.push(code);
+            },
+        }
+    }
+    Ok(out)
+}
+
+/// Expands `&`-code markup and writes the result followed by a newline to stdout.
+pub fn printc(s: impl AsRef<str>, mode: AnsiMode) -> Result<()> {
+    let colored = color(s, mode)?;
+    writeln!(io::stdout(), "{colored}")?;
+    Ok(())
+}
+
+/// Clears the screen and prints an optional (colored) title at the top.
+pub fn clear_screen(title: Option<&str>, mode: AnsiMode, debug_mode: bool) -> Result<()> {
+    if !debug_mode && color_enabled() {
+        write!(io::stdout(), "\x1b[2J\x1b[H")?;
+    }
+    if let Some(title) = title {
+        printc(title, mode)?;
+        writeln!(io::stdout())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_ansi16_index_matches_exact_palette_entries() {
+        assert_eq!(nearest_ansi16_index(Srgb::new(0, 0, 0)), (0, false));
+        assert_eq!(nearest_ansi16_index(Srgb::new(170, 0, 0)), (1, false));
+        assert_eq!(nearest_ansi16_index(Srgb::new(85, 85, 85)), (0, true));
+        assert_eq!(nearest_ansi16_index(Srgb::new(255, 255, 255)), (7, true));
+    }
+
+    #[test]
+    fn nearest_ansi256_index_picks_known_indices() {
+        // Exact 6x6x6 cube corners.
+        assert_eq!(nearest_ansi256_index(Srgb::new(0, 0, 0)), 16);
+        assert_eq!(nearest_ansi256_index(Srgb::new(255, 0, 0)), 196);
+        assert_eq!(nearest_ansi256_index(Srgb::new(255, 255, 255)), 231);
+        // Exact grayscale ramp entry (closer to it than to any cube corner).
+        assert_eq!(nearest_ansi256_index(Srgb::new(118, 118, 118)), 243);
+    }
+}
@@ -0,0 +1,26 @@
+//! Minimal distro ascii art registry, keyed by `/etc/os-release`'s `ID` field
+//! (or the user-supplied `--distro` override).
+
+/// A known distro's bundled ascii art.
+#[derive(Clone, Debug)]
+pub struct Distro {
+    pub name: &'static str,
+    pub ascii: String,
+}
+
+impl Distro {
+    /// Looks up a distro by (case-insensitive) name.
+    pub fn detect(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        let (key, ascii) = KNOWN.iter().find(|(key, _)| *key == name)?;
+        Some(Self { name: key, ascii: (*ascii).to_owned() })
+    }
+}
+
+const KNOWN: &[(&str, &str)] = &[
+    ("linux", "    .--.\n   |o_o |\n   |:_/ |\n  //   \\ \\\n (|     | )\n/'\\_   _/`\\\n\\___)=(___/\n"),
+    (
+        "arch",
+        "      /\\\n     /  \\\n    /\\   \\\n   /      \\\n  /   ,,   \\\n /   |  |  -\\\n/_-''    ''-_\\\n",
+    ),
+];
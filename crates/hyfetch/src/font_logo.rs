@@ -0,0 +1,20 @@
+//! The `--print-font-logo` big-text "hyfetch" wordmark.
+
+use anyhow::Result;
+
+use crate::types::Backend;
+
+const FONT_LOGO: &str = r"
+ _                __      _       _
+| |__  _   _  / _| ___| |_ ___| |__
+| '_ \| | | |/ _|/ _ \ __/ __| '_ \
+| | | | |_| | |  __/ ||  (__| | | |
+|_| |_|\__, |_|\___|\__\___|_| |_|
+       |___/
+";
+
+/// Returns the ascii-art wordmark shown by `--print-font-logo`.
+pub fn get_font_logo(backend: Backend) -> Result<String> {
+    let _ = backend;
+    Ok(FONT_LOGO.trim_matches('\n').to_owned())
+}
@@ -0,0 +1,16 @@
+pub mod answer_source;
+pub mod ascii;
+pub mod cli_options;
+pub mod color_util;
+pub mod distros;
+pub mod font_logo;
+pub mod models;
+pub mod neofetch_util;
+pub mod pager;
+pub mod presets;
+pub mod pride_month;
+pub mod term_caps;
+pub mod types;
+pub mod utils;
+pub mod vtcol;
+pub mod wizard;
@@ -0,0 +1,40 @@
+//! The persisted configuration written by the `create_config` wizard and
+//! loaded back on every subsequent run.
+
+use serde::{Deserialize, Serialize};
+
+use crate::color_util::Lightness;
+use crate::neofetch_util::ColorAlignment;
+use crate::pager::PagingMode;
+use crate::types::{AnsiMode, Backend, TerminalTheme};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// Name of the built-in or user-defined preset to use, resolved against
+    /// the [`PresetRegistry`](crate::presets::user::PresetRegistry) at
+    /// startup.
+    pub preset: String,
+    pub mode: AnsiMode,
+    pub light_dark: Option<TerminalTheme>,
+    pub auto_detect_light_dark: Option<bool>,
+    pub lightness: Option<Lightness>,
+    pub color_align: ColorAlignment,
+    pub backend: Backend,
+    pub args: Option<Vec<String>>,
+    pub distro: Option<String>,
+    #[serde(default)]
+    pub pride_month_disable: bool,
+    #[serde(default)]
+    pub paging: PagingMode,
+}
+
+impl Config {
+    /// The default lightness to use for a theme when the user hasn't picked one.
+    pub fn default_lightness(theme: TerminalTheme) -> Lightness {
+        let value = match theme {
+            TerminalTheme::Light => 0.65,
+            TerminalTheme::Dark => 0.75,
+        };
+        Lightness::new(value).expect("default lightness should be valid")
+    }
+}
@@ -0,0 +1,194 @@
+//! Invoking the *fetch backend and recoloring its (or our bundled) ascii art.
+
+use std::collections::HashMap;
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use anyhow::{Context as _, Result};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::answer_source::AnswerSource;
+use crate::ascii::RawAsciiArt;
+use crate::color_util::{
+    color, printc, ForegroundBackground, NeofetchAsciiIndexedColor, PresetIndexedColor,
+    ToAnsiString as _,
+};
+use crate::pager::{self, PagingMode};
+use crate::presets::ColorProfile;
+use crate::types::{AnsiMode, Backend};
+
+/// Test ascii art used by the brightness-preview step of the config wizard.
+/// Wrapped in newlines so the stripped first/last line match the slicing
+/// done by the caller.
+pub const TEST_ASCII: &str = "\n   /\\_/\\\n  ( {txt} )\n   > ^ <\n\n";
+
+/// Placeholder patterns recognized in bundled/neofetch ascii art, e.g. `${c1}`.
+pub const NEOFETCH_COLOR_PATTERNS: &[&str] =
+    &["${c1}", "${c2}", "${c3}", "${c4}", "${c5}", "${c6}"];
+
+pub static NEOFETCH_COLORS_AC: OnceLock<aho_corasick::AhoCorasick> = OnceLock::new();
+
+/// How ascii-art placeholder colors map onto the chosen preset's colors.
+#[derive(Clone, Debug, PartialEq, strum::AsRefStr, Serialize, Deserialize)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case", tag = "mode")]
+pub enum ColorAlignment {
+    Horizontal,
+    Vertical,
+    Custom {
+        colors: IndexMap<NeofetchAsciiIndexedColor, PresetIndexedColor>,
+    },
+}
+
+impl ColorAlignment {
+    /// Replaces every `${cN}` placeholder in `lines` with the ANSI escape for
+    /// the preset color it's aligned to.
+    pub fn recolor_ascii(
+        &self,
+        lines: &[String],
+        color_profile: &ColorProfile,
+        color_mode: AnsiMode,
+    ) -> Result<Vec<String>> {
+        let ac = NEOFETCH_COLORS_AC.get_or_init(|| {
+            aho_corasick::AhoCorasick::new(NEOFETCH_COLOR_PATTERNS).unwrap()
+        });
+
+        let mapping: HashMap<NeofetchAsciiIndexedColor, PresetIndexedColor> = match self {
+            Self::Custom { colors } => colors.iter().map(|(&k, &v)| (k, v)).collect(),
+            Self::Horizontal | Self::Vertical => (0..6u8)
+                .map(|i| {
+                    let preset_i = if color_profile.colors.is_empty() {
+                        0
+                    } else {
+                        i % u8::try_from(color_profile.colors.len()).unwrap_or(1)
+                    };
+                    (NeofetchAsciiIndexedColor(i.checked_add(1).unwrap()), PresetIndexedColor(preset_i))
+                })
+                .collect(),
+        };
+
+        lines
+            .iter()
+            .map(|line| {
+                let mut out = String::with_capacity(line.len());
+                let mut last = 0;
+                for m in ac.find_iter(line) {
+                    out.push_str(&line[last..m.start()]);
+                    let ai: NeofetchAsciiIndexedColor = line[m.start().checked_add(3).unwrap()..m.end().checked_sub(1).unwrap()]
+                        .parse()
+                        .context("neofetch ascii color index should not be invalid")?;
+                    if let Some(&PresetIndexedColor(pi)) = mapping.get(&ai) {
+                        let color = color_profile
+                            .colors
+                            .get(usize::from(pi))
+                            .copied()
+                            .unwrap_or_else(|| color_profile.colors[0]);
+                        out.push_str(&color.to_ansi_string(color_mode, ForegroundBackground::Foreground));
+                    }
+                    last = m.end();
+                }
+                out.push_str(&line[last..]);
+                Ok(out)
+            })
+            .collect()
+    }
+}
+
+/// Always succeeds without changing anything: `PATH` is left as it is and no
+/// bundled `pkg` directory is added to it.
+pub fn add_pkg_path() -> Result<()> {
+    Ok(())
+}
+
+/// Looks up `fastfetch` on `PATH`.
+pub fn fastfetch_path() -> Result<PathBuf> {
+    which::which("fastfetch").context("fastfetch not found on PATH")
+}
+
+/// Looks up `macchina` on `PATH`.
+#[cfg(feature = "macchina")]
+pub fn macchina_path() -> Result<Option<PathBuf>> {
+    Ok(which::which("macchina").ok())
+}
+
+/// Reads the current distro's name from the `ID` environment variable,
+/// defaulting to `linux` when it is unset. `backend` is not consulted.
+pub fn get_distro_name(backend: Backend) -> Result<String> {
+    let _ = backend;
+    Ok(env::var("ID").unwrap_or_else(|_| "linux".to_owned()))
+}
+
+/// Loads the bundled ascii art for `distro`, using `linux` when `distro` is
+/// `None`; a name with no bundled art gets [`TEST_ASCII`] instead. No
+/// detection is done and `backend` is not consulted.
+pub fn get_distro_ascii(distro: Option<&String>, backend: Backend) -> Result<RawAsciiArt> {
+    let _ = backend;
+    let name = distro.cloned().unwrap_or_else(|| "linux".to_owned());
+    let asc = crate::distros::Distro::detect(&name)
+        .map(|d| d.ascii)
+        .unwrap_or_else(|| TEST_ASCII.trim_matches('\n').to_owned());
+    Ok(RawAsciiArt { asc, fg: Vec::new() })
+}
+
+/// Prompts the user with `prompt`, accepting one of `choices` (case-insensitively).
+/// Reads the answer from `source`, so this can be driven non-interactively by
+/// a replayed answer list instead of stdin.
+pub fn literal_input(
+    prompt: impl AsRef<str>,
+    choices: &[impl AsRef<str>],
+    default: &str,
+    show_choices: bool,
+    color_mode: AnsiMode,
+    source: &mut dyn AnswerSource,
+) -> Result<String> {
+    loop {
+        let prompt = if show_choices {
+            format!(
+                "{prompt} ({choices}) [{default}] ",
+                prompt = prompt.as_ref(),
+                choices = choices.iter().map(|c| c.as_ref()).collect::<Vec<_>>().join("/"),
+            )
+        } else {
+            format!("{prompt} [{default}] ", prompt = prompt.as_ref())
+        };
+        printc(color(prompt, color_mode)?, color_mode)?;
+        let input = source.next_answer(Some("> "))?.trim().to_lowercase();
+        let input = if input.is_empty() { default.to_owned() } else { input };
+        if let Some(choice) = choices.iter().find(|c| c.as_ref().eq_ignore_ascii_case(&input)) {
+            return Ok(choice.as_ref().to_owned());
+        }
+        printc("&cInvalid choice, please try again.", color_mode)?;
+    }
+}
+
+/// Runs the *fetch `backend` over the recolored ascii art, paging the
+/// rendered art through `$PAGER` per `paging` when it doesn't fit on one
+/// screen (the backend's own output, e.g. neofetch's side info, is left
+/// un-paged since it's written directly to our inherited stdout).
+pub fn run(
+    asc: crate::ascii::NormalizedAsciiArt,
+    backend: Backend,
+    args: Option<&Vec<String>>,
+    paging: PagingMode,
+) -> Result<()> {
+    let mut rendered = asc.lines.join("\n");
+    rendered.push('\n');
+    pager::page(&rendered, paging).context("failed to page ascii art")?;
+    if let Backend::Neofetch | Backend::Fastfetch = backend {
+        let program = match backend {
+            Backend::Neofetch => "neofetch",
+            Backend::Fastfetch => "fastfetch",
+            #[cfg(feature = "macchina")]
+            Backend::Macchina => "macchina",
+        };
+        let mut command = Command::new(program);
+        if let Some(args) = args {
+            command.args(args);
+        }
+        command.status().with_context(|| format!("failed to run {program}"))?;
+    }
+    Ok(())
+}
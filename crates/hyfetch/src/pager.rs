@@ -0,0 +1,68 @@
+//! Piping long output through `$PAGER` (falling back to `less -R` so ANSI
+//! colors survive), for output that might exceed one screen.
+
+use std::env;
+use std::io::{self, IsTerminal as _, Write as _};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumString, VariantArray, VariantNames};
+use terminal_size::{terminal_size, Height};
+
+/// When to page output through `$PAGER`.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, AsRefStr, EnumString, VariantArray, VariantNames,
+    Serialize, Deserialize,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum PagingMode {
+    /// Always page, even if the content fits on one screen.
+    Always,
+    /// Page only when the content doesn't fit on one screen (the default).
+    #[default]
+    QuitIfOneScreen,
+    /// Never page; always print directly.
+    Never,
+}
+
+/// Prints `content` to stdout, piping it through `$PAGER` (or `less -R`) when
+/// `mode` and the terminal height call for it. Falls back to printing
+/// directly when stdout isn't a TTY, since there's nothing to page for a pipe
+/// or redirect to a file.
+pub fn page(content: &str, mode: PagingMode) -> Result<()> {
+    if mode == PagingMode::Never || !io::stdout().is_terminal() {
+        return print_directly(content);
+    }
+
+    if mode == PagingMode::QuitIfOneScreen {
+        let term_h = terminal_size().map_or(24, |(_, Height(h))| usize::from(h));
+        if content.lines().count() <= term_h {
+            return print_directly(content);
+        }
+    }
+
+    let pager_cmd = env::var("PAGER").unwrap_or_else(|_| "less -R".to_owned());
+    let mut parts = pager_cmd.split_whitespace();
+    let program = parts
+        .next()
+        .with_context(|| format!("`PAGER` ({pager_cmd:?}) should not be empty"))?;
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to spawn pager {pager_cmd:?}"))?;
+    child
+        .stdin
+        .take()
+        .context("pager child process should have a stdin pipe")?
+        .write_all(content.as_bytes())
+        .context("failed to write content to pager")?;
+    child.wait().context("failed to wait for pager to exit")?;
+    Ok(())
+}
+
+fn print_directly(content: &str) -> Result<()> {
+    write!(io::stdout(), "{content}").context("failed to write content to stdout")
+}
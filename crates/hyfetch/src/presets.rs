@@ -0,0 +1,407 @@
+//! Built-in pride flag presets and the [`ColorProfile`] they expand into.
+
+use std::str::FromStr;
+
+use anyhow::{Context as _, Result};
+use palette::{FromColor as _, Hsl, IntoColor as _, Lighten as _, Srgb};
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumCount, EnumString, VariantArray, VariantNames};
+
+use crate::color_util::{color_enabled, ForegroundBackground, ToAnsiString as _};
+use crate::types::{AnsiMode, TerminalTheme};
+
+/// A built-in flag preset.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, AsRefStr, EnumCount, EnumString, VariantArray, VariantNames,
+    Serialize, Deserialize,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    Rainbow,
+    Transgender,
+    Nonbinary,
+    Lesbian,
+    Gay,
+    Bisexual,
+    Pansexual,
+    Asexual,
+    Genderfluid,
+    Genderqueer,
+}
+
+impl Preset {
+    /// The built-in, unweighted RGB stripes for this preset.
+    pub fn color_profile(&self) -> ColorProfile {
+        let hex: &[&str] = match self {
+            Self::Rainbow => &["#E50000", "#FF8D00", "#FFEE00", "#028121", "#004CFF", "#770088"],
+            Self::Transgender => {
+                &["#5BCEFA", "#F5A9B8", "#FFFFFF", "#F5A9B8", "#5BCEFA"]
+            },
+            Self::Nonbinary => &["#FCF434", "#FFFFFF", "#9C59D1", "#2C2C2C"],
+            Self::Lesbian => {
+                &["#D52D00", "#FF9A56", "#FFFFFF", "#D362A4", "#A30262"]
+            },
+            Self::Gay => &[
+                "#078D70", "#98E8C1", "#FFFFFF", "#7BADE2", "#3D1A78",
+            ],
+            Self::Bisexual => &["#D60270", "#D60270", "#9B4F96", "#0038A8", "#0038A8"],
+            Self::Pansexual => &["#FF218C", "#FFD800", "#21B1FF"],
+            Self::Asexual => &["#000000", "#A3A3A3", "#FFFFFF", "#800080"],
+            Self::Genderfluid => {
+                &["#FE76A2", "#FFFFFF", "#BF12D7", "#000000", "#303CBE"]
+            },
+            Self::Genderqueer => &["#B57EDC", "#FFFFFF", "#4A8123"],
+        };
+        ColorProfile::from_hex_colors(hex).expect("built-in preset colors should be valid")
+    }
+}
+
+/// How a target lightness should be applied to a [`ColorProfile`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AssignLightness {
+    /// Replace every color's lightness with the given value.
+    Replace(crate::color_util::Lightness),
+}
+
+/// An ordered list of colors, optionally with per-stripe weights.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorProfile {
+    pub colors: Vec<Srgb<u8>>,
+    /// Relative stripe weights, parallel to `colors`. `None` means "evenly
+    /// spaced" (every stripe has the same width).
+    pub weights: Option<Vec<u32>>,
+}
+
+impl ColorProfile {
+    pub fn new(colors: Vec<Srgb<u8>>) -> Self {
+        Self { colors, weights: None }
+    }
+
+    /// Parses a list of `#rrggbb` or `r,g,b` color strings into a profile.
+    pub fn from_hex_colors(colors: &[impl AsRef<str>]) -> Result<Self> {
+        let colors = colors
+            .iter()
+            .map(|s| parse_color(s.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(colors))
+    }
+
+    /// Colors deduplicated in original order; used when sampling a fixed
+    /// number of representative stripes (e.g. the 16-color VT console palette).
+    pub fn unique_colors(&self) -> Self {
+        let mut seen = Vec::new();
+        for &c in &self.colors {
+            if !seen.contains(&c) {
+                seen.push(c);
+            }
+        }
+        Self::new(seen)
+    }
+
+    pub fn lighten(&self, scale: f32) -> Self {
+        let colors = self
+            .colors
+            .iter()
+            .map(|c| {
+                let hsl: Hsl = Hsl::from_color(c.into_format::<f32>());
+                Srgb::from_color(hsl.lighten(scale)).into_format()
+            })
+            .collect();
+        Self { colors, weights: self.weights.clone() }
+    }
+
+    pub fn with_lightness(&self, assign: AssignLightness) -> Self {
+        let AssignLightness::Replace(lightness) = assign;
+        let lightness = f32::from(lightness);
+        let colors = self
+            .colors
+            .iter()
+            .map(|c| {
+                let mut hsl: Hsl = Hsl::from_color(c.into_format::<f32>());
+                hsl.lightness = lightness;
+                Srgb::from_color(hsl).into_format()
+            })
+            .collect();
+        Self { colors, weights: self.weights.clone() }
+    }
+
+    /// Replaces every color's lightness with `lightness`, exactly like
+    /// [`Self::with_lightness`]; the terminal theme does not change the result.
+    pub fn with_lightness_adaptive(&self, lightness: crate::color_util::Lightness, _theme: TerminalTheme) -> Self {
+        self.with_lightness(AssignLightness::Replace(lightness))
+    }
+
+    /// Splits `text` into consecutive, equally sized chunks and colors each
+    /// chunk with the next of the profile's colors, in order.
+    pub fn color_text(
+        &self,
+        text: impl AsRef<str>,
+        mode: AnsiMode,
+        fb: ForegroundBackground,
+        reset: bool,
+    ) -> Result<String> {
+        let text = text.as_ref();
+        if self.colors.is_empty() {
+            return Ok(text.to_owned());
+        }
+        let chunk = text.chars().count().div_ceil(self.colors.len()).max(1);
+        let mut out = String::new();
+        let chars: Vec<char> = text.chars().collect();
+        for (i, c) in self.colors.iter().enumerate() {
+            let start = i * chunk;
+            if start >= chars.len() {
+                break;
+            }
+            let end = (start + chunk).min(chars.len());
+            out.push_str(&c.to_ansi_string(mode, fb));
+            out.extend(&chars[start..end]);
+        }
+        if reset && color_enabled() {
+            out.push_str("\x1b[0m");
+        }
+        Ok(out)
+    }
+}
+
+/// User-defined presets loaded from `~/.config/hyfetch/presets/*.toml`, merged
+/// with the built-in [`Preset`] set so they're selectable anywhere a preset
+/// name is accepted (the flag picker, `--preset`, etc.).
+pub mod user {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::Path;
+
+    use anyhow::{bail, Context as _, Result};
+    use indexmap::IndexMap;
+    use serde::Deserialize;
+    use strum::VariantNames as _;
+    use tracing::warn;
+
+    use super::{ColorProfile, Preset};
+
+    /// The on-disk shape of a single `*.toml` preset file.
+    #[derive(Debug, Deserialize)]
+    struct PresetFile {
+        /// Optional display name; if present it should match the filename
+        /// (sans extension), purely as a sanity check — the filename (the
+        /// stem used to select the preset) is always authoritative.
+        name: Option<String>,
+        /// Ordered `#rrggbb` or `r,g,b` colors.
+        #[serde(default)]
+        colors: Vec<String>,
+        /// Optional integer stripe weights, parallel to `colors`.
+        #[serde(default)]
+        weights: Option<Vec<u32>>,
+        /// Name of a built-in preset or another file preset to inherit
+        /// unspecified fields from.
+        extends: Option<String>,
+    }
+
+    /// A preset selectable by name, whether built-in or user-defined.
+    #[derive(Clone, Debug)]
+    pub enum AnyPreset {
+        Builtin(Preset),
+        Custom(String),
+    }
+
+    impl AnyPreset {
+        pub fn color_profile(&self, registry: &PresetRegistry) -> ColorProfile {
+            match self {
+                Self::Builtin(preset) => preset.color_profile(),
+                Self::Custom(name) => registry
+                    .get(name)
+                    .cloned()
+                    .expect("custom preset name should have been validated against the registry"),
+            }
+        }
+    }
+
+    /// The merged set of built-in and user-defined presets, keyed by name.
+    #[derive(Clone, Debug, Default)]
+    pub struct PresetRegistry {
+        custom: IndexMap<String, ColorProfile>,
+    }
+
+    impl PresetRegistry {
+        /// Scans `dir` for `*.toml` preset files and resolves their `extends`
+        /// chains (which may point at built-in presets or other files in the
+        /// same directory).
+        pub fn load(dir: &Path) -> Result<Self> {
+            let mut raw = HashMap::new();
+            if dir.is_dir() {
+                for entry in fs::read_dir(dir)
+                    .with_context(|| format!("failed to read preset dir {dir:?}"))?
+                {
+                    let entry = entry.with_context(|| format!("failed to read entry in {dir:?}"))?;
+                    let path = entry.path();
+                    if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+                        continue;
+                    }
+                    let stem = path
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .with_context(|| format!("preset file {path:?} has no valid file stem"))?
+                        .to_owned();
+                    if stem.parse::<Preset>().is_ok() {
+                        warn!(
+                            filename = %stem,
+                            "preset file's name collides with a built-in preset; skipping it \
+                             since the built-in would otherwise shadow it",
+                        );
+                        continue;
+                    }
+                    let content = fs::read_to_string(&path)
+                        .with_context(|| format!("failed to read preset file {path:?}"))?;
+                    let file: PresetFile = toml::from_str(&content)
+                        .with_context(|| format!("failed to parse preset file {path:?}"))?;
+                    if let Some(name) = &file.name {
+                        if name != &stem {
+                            warn!(
+                                filename = %stem,
+                                declared_name = %name,
+                                "preset file's `name` disagrees with its filename; using the \
+                                 filename as the selectable preset name",
+                            );
+                        }
+                    }
+                    raw.insert(stem, file);
+                }
+            }
+
+            let mut resolved = IndexMap::new();
+            let mut names: Vec<String> = raw.keys().cloned().collect();
+            names.sort();
+            for name in names {
+                resolve(&name, &raw, &mut resolved, &mut Vec::new())?;
+            }
+            Ok(Self { custom: resolved })
+        }
+
+        pub fn get(&self, name: &str) -> Option<&ColorProfile> {
+            self.custom.get(name)
+        }
+
+        /// All selectable preset names: built-ins first, then custom presets
+        /// in the order they were resolved.
+        pub fn names(&self) -> Vec<String> {
+            Preset::VARIANTS
+                .iter()
+                .map(|&s| s.to_owned())
+                .chain(self.custom.keys().cloned())
+                .collect()
+        }
+
+        /// Resolves a name to either a built-in or a custom preset.
+        pub fn parse(&self, name: &str) -> Result<AnyPreset> {
+            if let Ok(preset) = name.parse::<Preset>() {
+                return Ok(AnyPreset::Builtin(preset));
+            }
+            if self.custom.contains_key(name) {
+                return Ok(AnyPreset::Custom(name.to_owned()));
+            }
+            bail!("{name:?} is not a known built-in or custom preset")
+        }
+    }
+
+    /// Resolves `name`'s `extends` chain, deep-merging child-over-parent,
+    /// memoizing into `resolved` and detecting cycles via `stack`.
+    fn resolve(
+        name: &str,
+        raw: &HashMap<String, PresetFile>,
+        resolved: &mut IndexMap<String, ColorProfile>,
+        stack: &mut Vec<String>,
+    ) -> Result<ColorProfile> {
+        if let Some(profile) = resolved.get(name) {
+            return Ok(profile.clone());
+        }
+        if stack.contains(&name.to_owned()) {
+            stack.push(name.to_owned());
+            bail!("cycle detected in preset `extends` chain: {}", stack.join(" -> "));
+        }
+        let file = raw
+            .get(name)
+            .with_context(|| format!("preset {name:?} does not extend a known preset file"))?;
+
+        let base = match &file.extends {
+            None => None,
+            Some(parent) => {
+                stack.push(name.to_owned());
+                let base = if let Ok(preset) = parent.parse::<Preset>() {
+                    preset.color_profile()
+                } else if raw.contains_key(parent) {
+                    resolve(parent, raw, resolved, stack)?
+                } else {
+                    stack.pop();
+                    bail!("preset {name:?} extends unknown preset {parent:?}");
+                };
+                stack.pop();
+                Some(base)
+            },
+        };
+
+        let colors = if file.colors.is_empty() {
+            base.as_ref()
+                .with_context(|| format!("preset {name:?} has no colors and no `extends`"))?
+                .colors
+                .clone()
+        } else {
+            super::ColorProfile::from_hex_colors(&file.colors)
+                .with_context(|| format!("preset {name:?} has invalid colors"))?
+                .colors
+        };
+        let weights = file
+            .weights
+            .clone()
+            .or_else(|| base.as_ref().and_then(|b| b.weights.clone()));
+
+        let profile = ColorProfile { colors, weights };
+        resolved.insert(name.to_owned(), profile.clone());
+        Ok(profile)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn resolve_detects_extends_cycle() {
+            let file = |extends: &str| PresetFile {
+                name: None,
+                colors: Vec::new(),
+                weights: None,
+                extends: Some(extends.to_owned()),
+            };
+            let raw = HashMap::from([
+                ("a".to_owned(), file("b")),
+                ("b".to_owned(), file("c")),
+                ("c".to_owned(), file("a")),
+            ]);
+
+            let err = resolve("a", &raw, &mut IndexMap::new(), &mut Vec::new())
+                .expect_err("a 3-node `extends` cycle should be rejected");
+            assert!(
+                err.to_string().contains("cycle detected"),
+                "unexpected error: {err}"
+            );
+        }
+    }
+}
+
+fn parse_color(s: &str) -> Result<Srgb<u8>> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix('#') {
+        return Srgb::from_str(&format!("#{hex}")).with_context(|| format!("invalid hex color {s:?}"));
+    }
+    if let Some((r, rest)) = s.split_once(',') {
+        let (g, b) = rest
+            .split_once(',')
+            .with_context(|| format!("invalid r,g,b color {s:?}"))?;
+        return Ok(Srgb::new(
+            r.trim().parse().with_context(|| format!("invalid red channel in {s:?}"))?,
+            g.trim().parse().with_context(|| format!("invalid green channel in {s:?}"))?,
+            b.trim().parse().with_context(|| format!("invalid blue channel in {s:?}"))?,
+        ));
+    }
+    anyhow::bail!("color {s:?} is neither `#rrggbb` nor `r,g,b`")
+}
@@ -0,0 +1,23 @@
+//! The one-time-per-year pride month animation shown in June.
+
+use anyhow::{Context as _, Result};
+
+use crate::color_util::printc;
+use crate::presets::Preset;
+use crate::types::AnsiMode;
+
+/// Prints a short rainbow-colored banner. Run at most once a year (the
+/// caller tracks that via a cache-dir marker file).
+pub fn start_animation(color_mode: AnsiMode) -> Result<()> {
+    let color_profile = Preset::Rainbow.color_profile();
+    let banner = color_profile
+        .color_text(
+            "  hyfetch wishes you a happy pride month!  ",
+            color_mode,
+            crate::color_util::ForegroundBackground::Background,
+            true,
+        )
+        .context("failed to color pride month banner")?;
+    printc(banner, color_mode).context("failed to print pride month banner")?;
+    Ok(())
+}
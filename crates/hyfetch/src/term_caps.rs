@@ -0,0 +1,60 @@
+//! Detecting what color depth and background the current terminal actually
+//! supports, so the config wizard can pick sensible defaults without
+//! prompting.
+
+use std::env;
+use std::io::{self, IsTerminal as _};
+
+use palette::Srgb;
+use terminal_colorsaurus::{background_color, QueryOptions};
+use tracing::debug;
+
+use crate::types::AnsiMode;
+
+/// Picks the best [`AnsiMode`] the current terminal is likely to support,
+/// without asking the user. `COLORTERM=truecolor`/`24bit` is trusted first;
+/// otherwise the terminfo database's `max_colors` capability for `$TERM` is
+/// used, falling back to the conservative [`AnsiMode::Ansi16`] when neither
+/// signal is available (e.g. `$TERM` is unset, as in some CI environments).
+pub fn detect_color_support() -> AnsiMode {
+    if let Ok(colorterm) = env::var("COLORTERM") {
+        let colorterm = colorterm.to_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            debug!(%colorterm, "detected truecolor support via COLORTERM");
+            return AnsiMode::Rgb;
+        }
+    }
+
+    match terminfo_max_colors() {
+        Some(colors) if colors >= 256 => AnsiMode::Ansi256,
+        Some(colors) if colors >= 8 => AnsiMode::Ansi16,
+        _ => AnsiMode::Ansi16,
+    }
+}
+
+/// Reads the `max_colors` (`Co`/`colors`) capability from the terminfo
+/// database entry for `$TERM`, if one can be loaded.
+fn terminfo_max_colors() -> Option<u32> {
+    let db = terminfo::Database::from_env().ok()?;
+    let colors = db.get::<terminfo::capability::MaxColors>()?;
+    u32::try_from(colors.0).ok()
+}
+
+/// Queries the terminal's background color, so the config wizard can pick a
+/// sensible default `light_dark` theme without prompting. Returns `None` when
+/// stdout isn't a terminal or the terminal doesn't support the query.
+pub fn det_bg() -> Result<Option<Srgb<u8>>, terminal_colorsaurus::Error> {
+    if !io::stdout().is_terminal() {
+        return Ok(None);
+    }
+
+    background_color(QueryOptions::default())
+        .map(|terminal_colorsaurus::Color { r, g, b }| Some(Srgb::new(r, g, b).into_format()))
+        .or_else(|err| {
+            if matches!(err, terminal_colorsaurus::Error::UnsupportedTerminal) {
+                Ok(None)
+            } else {
+                Err(err)
+            }
+        })
+}
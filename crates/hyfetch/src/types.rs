@@ -0,0 +1,56 @@
+//! Small shared enums used throughout the CLI and library.
+
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumCount, EnumString, VariantArray, VariantNames};
+
+/// The color rendering mode used when emitting ANSI escape sequences.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, AsRefStr, EnumCount, EnumString, VariantArray,
+    VariantNames, Serialize, Deserialize,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum AnsiMode {
+    /// 24-bit truecolor (`\e[38;2;r;g;bm`).
+    #[default]
+    Rgb,
+    /// xterm 256-color palette (`\e[38;5;{idx}m`).
+    Ansi256,
+    /// The original 16-color palette (`\e[3{0-7}m` / `\e[9{0-7}m`).
+    Ansi16,
+}
+
+/// The *fetch backend used to render system info alongside the colored ASCII art.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AsRefStr, EnumCount, EnumString, VariantArray, VariantNames, Serialize, Deserialize)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum Backend {
+    Neofetch,
+    Fastfetch,
+    #[cfg(feature = "macchina")]
+    Macchina,
+}
+
+/// When to emit ANSI color/markup escape sequences.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AsRefStr, EnumCount, EnumString, VariantArray, VariantNames, Serialize, Deserialize)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorWhen {
+    /// Emit colors only when stdout is a TTY that supports at least basic color.
+    #[default]
+    Auto,
+    /// Always emit colors, even when piped.
+    Always,
+    /// Never emit colors; degrade to plain text.
+    Never,
+}
+
+/// Whether the terminal is using a light or dark background.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AsRefStr, EnumCount, EnumString, VariantArray, VariantNames, Serialize, Deserialize)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum TerminalTheme {
+    Light,
+    #[default]
+    Dark,
+}
@@ -0,0 +1,42 @@
+//! Small filesystem and stdio helpers shared across the binary.
+
+use std::io::{self, Write as _};
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result};
+
+/// The directory hyfetch caches transient state in (e.g. the pride month
+/// "already shown this year" marker).
+pub fn get_cache_path() -> Result<PathBuf> {
+    let dir = dirs::cache_dir().context("failed to determine cache directory")?;
+    Ok(dir.join("hyfetch"))
+}
+
+/// The default path the config file is loaded from/saved to.
+pub fn get_default_config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("hyfetch")
+        .join("config.json")
+}
+
+/// The directory user-defined preset files (`*.toml`) are loaded from.
+pub fn get_user_presets_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("hyfetch")
+        .join("presets")
+}
+
+/// Prompts (if `prompt` is given) and reads a line of input from stdin.
+pub fn input(prompt: Option<&str>) -> Result<String> {
+    if let Some(prompt) = prompt {
+        write!(io::stdout(), "{prompt}").context("failed to write prompt to stdout")?;
+        io::stdout().flush().context("failed to flush stdout")?;
+    }
+    let mut buf = String::new();
+    io::stdin()
+        .read_line(&mut buf)
+        .context("failed to read line from stdin")?;
+    Ok(buf.trim_end_matches(['\r', '\n']).to_owned())
+}
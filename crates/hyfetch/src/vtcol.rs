@@ -0,0 +1,148 @@
+//! Pushing the chosen preset's colors into the Linux virtual console's
+//! 16-color palette (`PIO_CMAP`), so the whole TTY is themed instead of just
+//! the fetch output. Only meaningful on a real VT, hence the `target_os`
+//! and `vtcol` feature gates.
+#![cfg(all(target_os = "linux", feature = "vtcol"))]
+
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::fd::AsRawFd as _;
+use std::sync::Mutex;
+
+use anyhow::{Context as _, Result};
+use palette::Srgb;
+
+use crate::presets::ColorProfile;
+
+/// `KDGKBTYPE`: reports the keyboard/console type; used only to verify the
+/// opened fd is actually a VT before touching its palette.
+const KDGKBTYPE: libc::c_ulong = 0x4B33;
+/// `PIO_CMAP`: installs a new 16-entry (R,G,B) console color map.
+const PIO_CMAP: libc::c_ulong = 0x4B71;
+/// `GIO_CMAP`: reads back the current 16-entry console color map.
+const GIO_CMAP: libc::c_ulong = 0x4B70;
+
+/// A snapshot of a VT's 16-color palette (48 bytes: 16 entries of R, G, B),
+/// so it can later be restored with [`restore_palette`].
+#[derive(Clone, Copy)]
+pub struct SavedPalette([u8; 48]);
+
+fn open_console() -> Result<File> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .context("failed to open /dev/tty")?;
+
+    let mut kb_type: libc::c_char = 0;
+    // SAFETY: `file` is a valid, open fd and `kb_type` is a valid out-pointer
+    // for the single-byte result `KDGKBTYPE` writes.
+    let rc = unsafe { libc::ioctl(file.as_raw_fd(), KDGKBTYPE, &mut kb_type) };
+    if rc != 0 {
+        return Err(io::Error::last_os_error())
+            .context("KDGKBTYPE ioctl failed; /dev/tty is not a Linux virtual console");
+    }
+    Ok(file)
+}
+
+/// Samples 16 representative colors out of `colors`, repeating entries when
+/// fewer than 16 unique colors are present and skipping some when more are.
+fn sample_16(colors: &[Srgb<u8>]) -> [Srgb<u8>; 16] {
+    let n = colors.len().max(1);
+    std::array::from_fn(|i| colors[(i * n / 16).min(n - 1)])
+}
+
+fn palette_buffer(color_profile: &ColorProfile) -> [u8; 48] {
+    let samples = sample_16(&color_profile.unique_colors().colors);
+    let mut buf = [0u8; 48];
+    for (i, c) in samples.iter().enumerate() {
+        buf[i * 3] = c.red;
+        buf[i * 3 + 1] = c.green;
+        buf[i * 3 + 2] = c.blue;
+    }
+    buf
+}
+
+/// Reads back the console's current 16-color palette, to be restored later
+/// with [`restore_palette`].
+pub fn snapshot_palette() -> Result<SavedPalette> {
+    let file = open_console()?;
+    let mut buf = [0u8; 48];
+    // SAFETY: `buf` is a 48-byte buffer, exactly what `GIO_CMAP` writes.
+    let rc = unsafe { libc::ioctl(file.as_raw_fd(), GIO_CMAP, buf.as_mut_ptr()) };
+    if rc != 0 {
+        return Err(io::Error::last_os_error()).context("GIO_CMAP ioctl failed");
+    }
+    Ok(SavedPalette(buf))
+}
+
+/// Installs `color_profile`'s colors as the console's 16-color palette.
+pub fn apply_palette(color_profile: &ColorProfile) -> Result<()> {
+    let file = open_console()?;
+    let buf = palette_buffer(color_profile);
+    // SAFETY: `buf` is a 48-byte buffer, exactly what `PIO_CMAP` expects.
+    let rc = unsafe { libc::ioctl(file.as_raw_fd(), PIO_CMAP, buf.as_ptr()) };
+    if rc != 0 {
+        return Err(io::Error::last_os_error()).context("PIO_CMAP ioctl failed");
+    }
+    Ok(())
+}
+
+/// Restores a palette previously captured with [`snapshot_palette`].
+pub fn restore_palette(saved: &SavedPalette) -> Result<()> {
+    let file = open_console()?;
+    // SAFETY: `saved.0` is a 48-byte buffer, exactly what `PIO_CMAP` expects.
+    let rc = unsafe { libc::ioctl(file.as_raw_fd(), PIO_CMAP, saved.0.as_ptr()) };
+    if rc != 0 {
+        return Err(io::Error::last_os_error()).context("PIO_CMAP ioctl failed");
+    }
+    Ok(())
+}
+
+/// The palette snapshotted before `apply_palette`, consulted by
+/// [`restore_on_signal`] so a `^C` mid-fetch still restores the console's
+/// original colors.
+static SAVED_PALETTE: Mutex<Option<SavedPalette>> = Mutex::new(None);
+
+/// Restores the snapshotted palette and exits. Registered as the `SIGINT`/
+/// `SIGTERM` handler by [`PaletteGuard::new`] so an interrupted fetch doesn't
+/// leave the console stuck with the fetch's palette. Best-effort: `ioctl`
+/// from a signal handler isn't strictly async-signal-safe, but this is the
+/// same trade-off most short-lived CLIs that touch terminal state make.
+extern "C" fn restore_on_signal(_signum: libc::c_int) {
+    if let Ok(guard) = SAVED_PALETTE.lock() {
+        if let Some(saved) = *guard {
+            let _ = restore_palette(&saved);
+        }
+    }
+    std::process::exit(130);
+}
+
+/// RAII guard that restores a snapshotted palette when dropped (a normal
+/// exit) and also on `SIGINT`/`SIGTERM` (an interrupted one), so `apply_palette`
+/// never permanently overwrites the console's palette.
+pub struct PaletteGuard(SavedPalette);
+
+impl PaletteGuard {
+    /// Takes ownership of a snapshot captured with [`snapshot_palette`] and
+    /// arms both the `Drop`-based and signal-based restore paths.
+    pub fn new(saved: SavedPalette) -> Self {
+        *SAVED_PALETTE
+            .lock()
+            .expect("palette mutex should not be poisoned") = Some(saved);
+        // SAFETY: `restore_on_signal` only touches `SAVED_PALETTE` and calls
+        // `restore_palette`/`process::exit`, and stays registered for the
+        // rest of the process's lifetime.
+        unsafe {
+            libc::signal(libc::SIGINT, restore_on_signal as libc::sighandler_t);
+            libc::signal(libc::SIGTERM, restore_on_signal as libc::sighandler_t);
+        }
+        Self(saved)
+    }
+}
+
+impl Drop for PaletteGuard {
+    fn drop(&mut self) {
+        let _ = restore_palette(&self.0);
+    }
+}
@@ -0,0 +1,136 @@
+//! Golden-file test for the `Config` a wizard run produces.
+//!
+//! This drives the real [`hyfetch::wizard::create_config`] end-to-end through
+//! a [`ReplayAnswerSource`], the same entry point `--config`/`--config-answers`
+//! use, and checks its serialized JSON against a fixture in `tests/golden/`
+//! for a few distro/theme/preset combinations.
+//!
+//! `create_config` falls back to an 80x24 terminal size when none can be
+//! detected (as in this test harness), which is smaller than the wizard's
+//! minimum, so every run also answers the "terminal is too small" prompt.
+//! `COLORTERM` is cleared so color-mode auto-detection never short-circuits
+//! the color mode prompt, and stdout isn't a TTY under `cargo test`, so the
+//! background-color auto-detection never short-circuits the theme prompt
+//! either -- both prompts always fire, keeping the answer list's shape fixed
+//! across scenarios.
+//!
+//! Run with `UPDATE_GOLDEN=1 cargo test -p hyfetch --test config_golden` to
+//! regenerate the fixtures after an intentional `Config` shape change.
+
+use std::fs;
+use std::path::PathBuf;
+
+use hyfetch::answer_source::ReplayAnswerSource;
+use hyfetch::models::Config;
+use hyfetch::presets::user::PresetRegistry;
+use hyfetch::types::Backend;
+use hyfetch::wizard::create_config;
+
+struct Scenario {
+    fixture: &'static str,
+    distro: Option<&'static str>,
+    /// Answers in the order `create_config` asks for them: the "terminal too
+    /// small" press-enter, color mode, theme, preset, lightness percentage,
+    /// color alignment, backend, and finally "Save config?".
+    answers: [&'static str; 8],
+}
+
+const SCENARIOS: &[Scenario] = &[
+    Scenario {
+        fixture: "arch_dark_rainbow.json",
+        distro: Some("arch"),
+        answers: [
+            "",
+            "rgb",
+            "dark",
+            "rainbow",
+            "75",
+            "horizontal",
+            "neofetch",
+            "n",
+        ],
+    },
+    Scenario {
+        fixture: "linux_light_transgender.json",
+        distro: Some("linux"),
+        answers: [
+            "",
+            "ansi256",
+            "light",
+            "transgender",
+            "65",
+            "vertical",
+            "fastfetch",
+            "n",
+        ],
+    },
+    Scenario {
+        fixture: "no_distro_dark_nonbinary.json",
+        distro: None,
+        answers: [
+            "",
+            "ansi16",
+            "dark",
+            "nonbinary",
+            "80",
+            "horizontal",
+            "neofetch",
+            "n",
+        ],
+    },
+];
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name)
+}
+
+#[test]
+fn config_json_matches_golden_fixtures() {
+    // Guarantee the color mode prompt always fires, regardless of what the
+    // test process inherited from its environment.
+    std::env::remove_var("COLORTERM");
+
+    let registry = PresetRegistry::default();
+    let config_path = std::env::temp_dir().join("hyfetch-golden-test-config.json");
+
+    for scenario in SCENARIOS {
+        let distro = scenario.distro.map(ToOwned::to_owned);
+        let mut source = ReplayAnswerSource::new(scenario.answers.iter().map(|s| s.to_string()));
+
+        let config = create_config(
+            &config_path,
+            distro.as_ref(),
+            Backend::Neofetch,
+            false,
+            &mut source,
+            &registry,
+        )
+        .expect("create_config should succeed with a fully-answered replay source");
+
+        assert_golden(&config, scenario.fixture);
+    }
+}
+
+fn assert_golden(config: &Config, fixture: &str) {
+    let actual = serde_json::to_string_pretty(config).expect("Config should serialize to JSON");
+    let path = fixture_path(fixture);
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, format!("{actual}\n")).expect("failed to write golden fixture");
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("failed to read golden fixture {path:?}: {err}"));
+    let actual_value: serde_json::Value =
+        serde_json::from_str(&actual).expect("actual JSON should parse");
+    let expected_value: serde_json::Value = serde_json::from_str(&expected)
+        .unwrap_or_else(|err| panic!("golden fixture {path:?} is not valid JSON: {err}"));
+    assert_eq!(
+        actual_value, expected_value,
+        "serialized Config for {fixture:?} doesn't match its golden fixture; rerun with \
+         UPDATE_GOLDEN=1 if this is an intentional Config shape change",
+    );
+}